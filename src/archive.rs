@@ -0,0 +1,139 @@
+use crate::extractor::LinkedResource;
+use crate::fetch::{self, FetchOptions};
+use crate::sanitize_filename;
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use url::Url;
+
+/// Downloads every linked resource into `dir`, naming files by sanitized
+/// title with an extension detected from the file's own magic bytes
+/// (falling back to the URL's extension), and rewrites `content` so
+/// Markdown links point at the local copies instead of the remote URLs.
+pub fn run(resources: Vec<LinkedResource>, content: String, dir: &str, timeout: Duration) -> Result<String> {
+    if resources.is_empty() {
+        return Ok(content);
+    }
+
+    fs::create_dir_all(dir)?;
+    let client = reqwest::blocking::Client::new();
+    let opts = FetchOptions::new(timeout);
+
+    let mut rewrites: Vec<(String, String)> = Vec::new();
+    for resource in resources {
+        match archive_one(&client, &resource, dir, &opts) {
+            Ok(local_path) => {
+                println!("Archived {} -> {}", resource.url, local_path);
+                rewrites.push((resource.url, local_path));
+            }
+            Err(err) => eprintln!("Failed to archive {}: {:#}", resource.url, err),
+        }
+    }
+
+    Ok(rewrite_links(content, rewrites))
+}
+
+/// Rewrites each Markdown link target `](url)` to `](local_path)`,
+/// longest URL first. Matching on the whole link target, closing
+/// parenthesis included, keeps a hierarchical link (`.../swiftui`) from
+/// matching inside a longer one (`.../swiftui/view`), and leaves an
+/// identical URL that appears outside a link untouched.
+fn rewrite_links(content: String, mut rewrites: Vec<(String, String)>) -> String {
+    rewrites.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+
+    let mut content = content;
+    for (url, local_path) in rewrites {
+        content = content.replace(&format!("]({url})"), &format!("]({local_path})"));
+    }
+    content
+}
+
+fn archive_one(
+    client: &reqwest::blocking::Client,
+    resource: &LinkedResource,
+    dir: &str,
+    opts: &FetchOptions,
+) -> Result<String> {
+    let mut response = fetch::send_with_retry(|| client.get(&resource.url), opts)?;
+
+    let mut bytes = Vec::new();
+    response.copy_to(&mut bytes)?;
+
+    let extension = sniff_extension(&bytes).unwrap_or_else(|| extension_from_url(&resource.url));
+    let filename = format!("{}.{}", sanitize_filename(&resource.title), extension);
+    let path = Path::new(dir).join(&filename);
+    fs::write(&path, &bytes)?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Identifies a file's real type by its leading bytes, since servers
+/// often send a generic `Content-Type` for sample-code/PDF/image downloads.
+fn sniff_extension(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"PK\x03\x04") {
+        Some("zip")
+    } else if bytes.starts_with(b"%PDF") {
+        Some("pdf")
+    } else if bytes.starts_with(b"\x89PNG") {
+        Some("png")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("gif")
+    } else if bytes.starts_with(b"\xFF\xD8\xFF") {
+        Some("jpg")
+    } else {
+        None
+    }
+}
+
+fn extension_from_url(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.path_segments()?.last().map(str::to_string))
+        .and_then(|filename| filename.rsplit_once('.').map(|(_, ext)| ext.to_string()))
+        .filter(|ext| !ext.is_empty())
+        .unwrap_or_else(|| "bin".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniff_extension_recognizes_known_magic_bytes() {
+        assert_eq!(sniff_extension(b"PK\x03\x04rest-of-zip"), Some("zip"));
+        assert_eq!(sniff_extension(b"%PDF-1.4"), Some("pdf"));
+        assert_eq!(sniff_extension(b"\x89PNG\r\n\x1a\n"), Some("png"));
+        assert_eq!(sniff_extension(b"GIF89a"), Some("gif"));
+        assert_eq!(sniff_extension(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("jpg"));
+    }
+
+    #[test]
+    fn sniff_extension_returns_none_for_unrecognized_bytes() {
+        assert_eq!(sniff_extension(b"plain text content"), None);
+        assert_eq!(sniff_extension(b""), None);
+    }
+
+    #[test]
+    fn extension_from_url_reads_the_trailing_segment() {
+        assert_eq!(extension_from_url("https://example.com/foo/bar.zip"), "zip");
+        assert_eq!(extension_from_url("https://example.com/foo/bar"), "bin");
+        assert_eq!(extension_from_url("not a url"), "bin");
+    }
+
+    #[test]
+    fn rewrite_links_handles_url_that_is_a_prefix_of_another() {
+        let content = "[SwiftUI](https://developer.apple.com/documentation/swiftui) and \
+                        [View](https://developer.apple.com/documentation/swiftui/view)"
+            .to_string();
+        let rewrites = vec![
+            ("https://developer.apple.com/documentation/swiftui".to_string(), "archive/swiftui.html".to_string()),
+            ("https://developer.apple.com/documentation/swiftui/view".to_string(), "archive/view.html".to_string()),
+        ];
+
+        let rewritten = rewrite_links(content, rewrites);
+
+        assert!(rewritten.contains("[SwiftUI](archive/swiftui.html)"));
+        assert!(rewritten.contains("[View](archive/view.html)"));
+    }
+}
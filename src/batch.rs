@@ -0,0 +1,181 @@
+use crate::extractor::{find_extractor, Extractor};
+use crate::extractors::{self, fetch_html, Config};
+use crate::output::OutputFormat;
+use crate::sanitize_filename;
+use anyhow::{Context, Result};
+use scraper::{Html, Selector};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// One session's outcome, used to render the combined index.
+struct BatchEntry {
+    url: String,
+    title: Option<String>,
+    output_file: Option<String>,
+    error: Option<String>,
+}
+
+/// Crawls `source` (a topic/year index page URL, or a local file of
+/// URLs, one per line) for session links and extracts each one with a
+/// worker pool bounded by `concurrency`, writing one output file per
+/// session plus a combined index file.
+pub fn run(source: &str, format: OutputFormat, concurrency: usize, config: Config, toc: bool) -> Result<()> {
+    // Worker threads fetch concurrently, so per-fetch progress spinners
+    // would interleave into garbled output; force them off regardless of
+    // what the caller set.
+    let config = Config { show_progress: false, ..config };
+
+    let urls = discover_urls(source, &config)?;
+    if urls.is_empty() {
+        println!("No session URLs discovered from {}", source);
+        return Ok(());
+    }
+
+    let workers = concurrency.max(1);
+    println!("Discovered {} session(s); extracting with {} worker(s)", urls.len(), workers);
+
+    let chunks = split_round_robin(&urls, workers);
+    let entries: Vec<BatchEntry> = std::thread::scope(|scope| {
+        chunks.into_iter()
+            .map(|chunk| scope.spawn(|| process_chunk(chunk, &format, &config, toc)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("extraction worker panicked"))
+            .collect()
+    });
+
+    write_index(&entries, &format)?;
+
+    let failed = entries.iter().filter(|e| e.error.is_some()).count();
+    println!("Batch complete: {} succeeded, {} failed", entries.len() - failed, failed);
+    Ok(())
+}
+
+/// Splits `urls` into up to `workers` chunks, assigning round-robin so
+/// each worker thread gets roughly even work.
+fn split_round_robin(urls: &[String], workers: usize) -> Vec<Vec<String>> {
+    let mut chunks = vec![Vec::new(); workers];
+    for (i, url) in urls.iter().enumerate() {
+        chunks[i % workers].push(url.clone());
+    }
+    chunks.into_iter().filter(|chunk| !chunk.is_empty()).collect()
+}
+
+fn process_chunk(urls: Vec<String>, format: &OutputFormat, config: &Config, toc: bool) -> Vec<BatchEntry> {
+    let extractors = extractors::all(config.clone());
+    urls.into_iter()
+        .map(|url| process_one(&extractors, url, format, toc))
+        .collect()
+}
+
+fn process_one(extractors: &[Box<dyn Extractor>], url_str: String, format: &OutputFormat, toc: bool) -> BatchEntry {
+    let outcome = (|| -> Result<(String, String)> {
+        let url = Url::parse(&url_str).context("Invalid URL")?;
+        let extractor = find_extractor(extractors, &url)
+            .with_context(|| format!("No extractor registered for {}", url))?;
+        let document = extractor.extract(&url)?;
+
+        let content = match format {
+            OutputFormat::Markdown => document.to_markdown(toc).content,
+            OutputFormat::Json => document.to_json()?,
+            OutputFormat::Yaml => document.to_yaml()?,
+        };
+
+        let base_name = format!("wwdc_{}_{}", extractor.name(), sanitize_filename(document.title()));
+        let filename = format!("{}.{}", base_name, format.extension());
+        fs::write(&filename, content)?;
+
+        Ok((document.title().to_string(), filename))
+    })();
+
+    match outcome {
+        Ok((title, output_file)) => {
+            println!("Generated {} file: {}", format.extension(), output_file);
+            BatchEntry { url: url_str, title: Some(title), output_file: Some(output_file), error: None }
+        }
+        Err(err) => {
+            eprintln!("Failed to extract {}: {:#}", url_str, err);
+            BatchEntry { url: url_str, title: None, output_file: None, error: Some(err.to_string()) }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct IndexEntry<'a> {
+    url: &'a str,
+    title: Option<&'a str>,
+    output_file: Option<&'a str>,
+    error: Option<&'a str>,
+}
+
+fn write_index(entries: &[BatchEntry], format: &OutputFormat) -> Result<()> {
+    let index_path = format!("wwdc_batch_index.{}", format.extension());
+
+    let content = match format {
+        OutputFormat::Markdown => {
+            let mut md = String::from("# Batch Index\n\n");
+            for entry in entries {
+                match (&entry.title, &entry.output_file) {
+                    (Some(title), Some(file)) => md.push_str(&format!("- [{}]({}) — {}\n", title, file, entry.url)),
+                    _ => md.push_str(&format!(
+                        "- FAILED: {} ({})\n",
+                        entry.url,
+                        entry.error.as_deref().unwrap_or("unknown error")
+                    )),
+                }
+            }
+            md
+        }
+        OutputFormat::Json => serde_json::to_string_pretty(&index_entries(entries))?,
+        OutputFormat::Yaml => serde_yaml::to_string(&index_entries(entries))?,
+    };
+
+    fs::write(&index_path, content)?;
+    println!("Generated batch index: {}", index_path);
+    Ok(())
+}
+
+fn index_entries(entries: &[BatchEntry]) -> Vec<IndexEntry<'_>> {
+    entries.iter()
+        .map(|e| IndexEntry {
+            url: &e.url,
+            title: e.title.as_deref(),
+            output_file: e.output_file.as_deref(),
+            error: e.error.as_deref(),
+        })
+        .collect()
+}
+
+/// Resolves `source` into a list of session URLs: a local file is read
+/// one URL per line, otherwise `source` is fetched as a listing page and
+/// every session link on it is collected.
+fn discover_urls(source: &str, config: &Config) -> Result<Vec<String>> {
+    if Path::new(source).is_file() {
+        let text = fs::read_to_string(source)?;
+        return Ok(text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect());
+    }
+
+    let listing_url = Url::parse(source).context("Batch source is neither a local file nor a valid URL")?;
+    let html = fetch_html(listing_url.as_str(), config, |opts| {
+        let client = reqwest::blocking::Client::new();
+        Ok(crate::fetch::send_with_retry(|| client.get(listing_url.as_str()), opts)?.text()?)
+    })?;
+    let document = Html::parse_document(&html);
+
+    let link_selector = Selector::parse("a[href*='/videos/play/']").unwrap();
+    let mut urls: Vec<String> = document.select(&link_selector)
+        .filter_map(|el| el.value().attr("href"))
+        .filter_map(|href| listing_url.join(href).ok())
+        .map(|url| url.to_string())
+        .collect();
+
+    urls.sort();
+    urls.dedup();
+    Ok(urls)
+}
@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One cached fetch: the raw HTML plus enough metadata to tell when and
+/// from where it was fetched. There's no HTTP status here: the
+/// headless-Chrome extractor has no response code to report, and a
+/// fetch that reached `put` always succeeded, so a status would only
+/// ever read "200" — not worth storing.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    fetched_at_unix: u64,
+    html: String,
+}
+
+/// On-disk HTTP cache keyed by a hash of the URL, so `--cache DIR` lets
+/// selector work iterate against saved fixtures instead of re-fetching
+/// live pages every run.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: &str) -> Result<Self> {
+        fs::create_dir_all(dir).with_context(|| format!("Creating cache dir {dir}"))?;
+        Ok(Self { dir: PathBuf::from(dir) })
+    }
+
+    pub fn get(&self, url: &str) -> Result<Option<String>> {
+        let path = self.path_for(url);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let raw = fs::read_to_string(&path).with_context(|| format!("Reading cache entry {}", path.display()))?;
+        let entry: CacheEntry = serde_json::from_str(&raw)?;
+        Ok(Some(entry.html))
+    }
+
+    pub fn put(&self, url: &str, html: &str) -> Result<()> {
+        let entry = CacheEntry {
+            url: url.to_string(),
+            fetched_at_unix: now_unix(),
+            html: html.to_string(),
+        };
+
+        let path = self.path_for(url);
+        fs::write(&path, serde_json::to_string_pretty(&entry)?)
+            .with_context(|| format!("Writing cache entry {}", path.display()))
+    }
+
+    fn path_for(&self, url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
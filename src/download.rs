@@ -0,0 +1,325 @@
+use crate::extractor::MediaSources;
+use crate::fetch::{self, FetchOptions};
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use url::Url;
+
+/// Output container for the muxed video, selected via `--container`.
+/// `ffmpeg` picks the muxer from the output file's extension, so this
+/// only needs to carry that extension through.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Container {
+    Mp4,
+    Mkv,
+}
+
+impl Container {
+    fn extension(&self) -> &'static str {
+        match self {
+            Container::Mp4 => "mp4",
+            Container::Mkv => "mkv",
+        }
+    }
+}
+
+/// One variant stream advertised by an HLS master playlist.
+struct Variant {
+    bandwidth: u64,
+    resolution: Option<String>,
+    url: Url,
+    /// `AUDIO` group id the variant references, if it doesn't carry its
+    /// own audio and instead points at an `EXT-X-MEDIA:TYPE=AUDIO` group.
+    audio_group: Option<String>,
+}
+
+/// One `EXT-X-MEDIA:TYPE=AUDIO` rendition advertised alongside the
+/// variants, keyed by its `GROUP-ID`.
+struct AudioRendition {
+    group_id: String,
+    url: Url,
+}
+
+/// Downloads the HLS stream and WebVTT captions for a session, muxing
+/// the result into `<base_name>.<container>` next to the generated
+/// Markdown/JSON/YAML file, with the captions saved alongside as
+/// `<base_name>.vtt`.
+///
+/// Only HLS is supported; WWDC does not serve DASH manifests.
+pub fn run(media: &MediaSources, quality: &str, container: Container, base_name: &str, timeout: Duration) -> Result<()> {
+    let client = reqwest::blocking::Client::new();
+    let opts = FetchOptions::new(timeout);
+
+    match &media.hls_manifest {
+        Some(manifest_url) => {
+            let manifest_url = Url::parse(manifest_url).context("Invalid HLS manifest URL")?;
+            download_video(&client, &manifest_url, quality, container, base_name, &opts)?;
+        }
+        None => println!("No HLS manifest found for this session; skipping video download"),
+    }
+
+    if let Some(captions_url) = &media.captions_url {
+        let vtt_path = format!("{}.vtt", base_name);
+        download_captions(&client, captions_url, &vtt_path, &opts)?;
+        println!("Generated captions file: {}", vtt_path);
+    }
+
+    Ok(())
+}
+
+fn download_video(
+    client: &reqwest::blocking::Client,
+    manifest_url: &Url,
+    quality: &str,
+    container: Container,
+    base_name: &str,
+    opts: &FetchOptions,
+) -> Result<()> {
+    let playlist_text = fetch::send_with_retry(|| client.get(manifest_url.as_str()), opts)?.text()?;
+
+    let (variants, renditions) = parse_master_playlist(&playlist_text, manifest_url)?;
+    let variant = select_variant(&variants, quality)
+        .with_context(|| format!("No HLS variant matches the requested --quality {quality}"))?;
+
+    let video_dir = PathBuf::from(format!("{}.video.segments", base_name));
+    let video_list = download_segments(client, &variant.url, &video_dir, opts)?;
+
+    // WWDC's HLS playlists often carry audio as a separate EXT-X-MEDIA
+    // rendition rather than muxed into the video variant; download and
+    // mux that too so the output isn't silently video-only.
+    let audio = match &variant.audio_group {
+        Some(group_id) => {
+            let rendition = renditions.iter()
+                .find(|r| &r.group_id == group_id)
+                .with_context(|| format!("Variant references AUDIO group {group_id:?} with no matching EXT-X-MEDIA rendition"))?;
+            let audio_dir = PathBuf::from(format!("{}.audio.segments", base_name));
+            let audio_list = download_segments(client, &rendition.url, &audio_dir, opts)?;
+            Some((audio_dir, audio_list))
+        }
+        None => None,
+    };
+
+    let output_path = format!("{}.{}", base_name, container.extension());
+    mux_segments(&video_list, audio.as_ref().map(|(_, list)| list.as_path()), &output_path)?;
+
+    std::fs::remove_dir_all(&video_dir).ok();
+    if let Some((audio_dir, _)) = &audio {
+        std::fs::remove_dir_all(audio_dir).ok();
+    }
+
+    println!("Generated video file: {}", output_path);
+    Ok(())
+}
+
+/// Downloads every segment of one HLS media playlist into `dir`,
+/// returning the path to the ffmpeg `concat` demuxer list file.
+fn download_segments(
+    client: &reqwest::blocking::Client,
+    media_playlist_url: &Url,
+    dir: &Path,
+    opts: &FetchOptions,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+
+    let segment_urls = fetch_segment_urls(client, media_playlist_url, opts)?;
+
+    let list_path = dir.join("segments.txt");
+    let mut list_file = BufWriter::new(File::create(&list_path)?);
+
+    for (i, segment_url) in segment_urls.iter().enumerate() {
+        let segment_path = dir.join(format!("segment_{:05}.ts", i));
+        download_segment(client, segment_url, &segment_path, opts)?;
+        writeln!(list_file, "file '{}'", segment_path.display())?;
+    }
+    list_file.flush()?;
+
+    Ok(list_path)
+}
+
+/// Streams one HLS segment straight to disk via a `Range` request, so a
+/// multi-gigabyte session never has to be held in memory at once.
+fn download_segment(client: &reqwest::blocking::Client, url: &Url, dest: &Path, opts: &FetchOptions) -> Result<()> {
+    let mut response = fetch::send_with_retry(|| {
+        client.get(url.as_str()).header("Range", "bytes=0-")
+    }, opts)?;
+
+    let mut file = File::create(dest)?;
+    response.copy_to(&mut file)?;
+    Ok(())
+}
+
+fn download_captions(client: &reqwest::blocking::Client, url: &str, dest: &str, opts: &FetchOptions) -> Result<()> {
+    let mut response = fetch::send_with_retry(|| client.get(url), opts)?;
+    let mut file = File::create(dest)?;
+    response.copy_to(&mut file)?;
+    Ok(())
+}
+
+fn fetch_segment_urls(client: &reqwest::blocking::Client, media_playlist_url: &Url, opts: &FetchOptions) -> Result<Vec<Url>> {
+    let text = fetch::send_with_retry(|| client.get(media_playlist_url.as_str()), opts)?.text()?;
+
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| media_playlist_url.join(line).context("Invalid segment URI"))
+        .collect()
+}
+
+fn parse_master_playlist(text: &str, manifest_url: &Url) -> Result<(Vec<Variant>, Vec<AudioRendition>)> {
+    let mut variants = Vec::new();
+    let mut renditions = Vec::new();
+    let mut lines = text.lines();
+
+    while let Some(line) = lines.next() {
+        if line.starts_with("#EXT-X-MEDIA:") && line.contains("TYPE=AUDIO") {
+            let group_id = line.split(',')
+                .find_map(|attr| attr.strip_prefix("GROUP-ID="))
+                .map(|v| v.trim_matches('"').to_string());
+            let uri = line.split(',')
+                .find_map(|attr| attr.strip_prefix("URI="))
+                .map(|v| v.trim_matches('"').to_string());
+
+            if let (Some(group_id), Some(uri)) = (group_id, uri) {
+                renditions.push(AudioRendition {
+                    group_id,
+                    url: manifest_url.join(&uri).context("Invalid audio rendition URI")?,
+                });
+            }
+            continue;
+        }
+
+        let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") else {
+            continue;
+        };
+
+        let bandwidth = attrs.split(',')
+            .find_map(|attr| attr.strip_prefix("BANDWIDTH="))
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0);
+
+        let resolution = attrs.split(',')
+            .find_map(|attr| attr.strip_prefix("RESOLUTION="))
+            .map(|v| v.trim().to_string());
+
+        let audio_group = attrs.split(',')
+            .find_map(|attr| attr.strip_prefix("AUDIO="))
+            .map(|v| v.trim().trim_matches('"').to_string());
+
+        let Some(uri_line) = lines.next() else {
+            continue;
+        };
+        let uri_line = uri_line.trim();
+        if uri_line.is_empty() {
+            continue;
+        }
+
+        variants.push(Variant {
+            bandwidth,
+            resolution,
+            url: manifest_url.join(uri_line).context("Invalid variant URI")?,
+            audio_group,
+        });
+    }
+
+    if variants.is_empty() {
+        bail!("Master playlist had no variant streams");
+    }
+
+    Ok((variants, renditions))
+}
+
+/// Picks the variant matching `quality`. An explicit `RESOLUTION` string
+/// that the manifest doesn't advertise is an error rather than a silent
+/// fallback to another resolution the user didn't ask for.
+fn select_variant<'a>(variants: &'a [Variant], quality: &str) -> Option<&'a Variant> {
+    match quality {
+        "best" => variants.iter().max_by_key(|v| v.bandwidth),
+        "worst" => variants.iter().min_by_key(|v| v.bandwidth),
+        resolution => variants.iter().find(|v| v.resolution.as_deref() == Some(resolution)),
+    }
+}
+
+/// Muxes one `ffmpeg concat`-demuxer video list, plus an optional
+/// separate audio list (for renditions whose audio isn't already muxed
+/// into the video segments), into a single file at `output_path`, whose
+/// extension decides the container.
+fn mux_segments(video_list: &Path, audio_list: Option<&Path>, output_path: &str) -> Result<()> {
+    let mut command = Command::new("ffmpeg");
+    command.args(["-y", "-f", "concat", "-safe", "0", "-i"]).arg(video_list);
+
+    if let Some(audio_list) = audio_list {
+        command.args(["-f", "concat", "-safe", "0", "-i"]).arg(audio_list);
+        command.args(["-map", "0:v:0", "-map", "1:a:0"]);
+    }
+
+    command.args(["-c", "copy"]).arg(output_path);
+
+    let status = command.status()
+        .context("Failed to spawn ffmpeg; is it installed and on PATH?")?;
+
+    if !status.success() {
+        bail!("ffmpeg exited with status {}", status);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTER_PLAYLIST: &str = "\
+#EXTM3U
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio\",NAME=\"stereo\",URI=\"audio/prog_index.m3u8\"
+#EXT-X-STREAM-INF:BANDWIDTH=800000,RESOLUTION=960x540,AUDIO=\"audio\"
+low/prog_index.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=5000000,RESOLUTION=1920x1080,AUDIO=\"audio\"
+high/prog_index.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=2000000,RESOLUTION=1280x720,AUDIO=\"audio\"
+mid/prog_index.m3u8
+";
+
+    fn manifest_url() -> Url {
+        Url::parse("https://example.com/hls/master.m3u8").unwrap()
+    }
+
+    #[test]
+    fn parse_master_playlist_reads_bandwidth_despite_leading_tag() {
+        let (variants, renditions) = parse_master_playlist(MASTER_PLAYLIST, &manifest_url()).unwrap();
+
+        let bandwidths: Vec<u64> = variants.iter().map(|v| v.bandwidth).collect();
+        assert_eq!(bandwidths, vec![800_000, 5_000_000, 2_000_000]);
+        assert_eq!(renditions.len(), 1);
+        assert_eq!(renditions[0].group_id, "audio");
+    }
+
+    #[test]
+    fn select_variant_best_and_worst_pick_by_bandwidth_not_playlist_order() {
+        let (variants, _) = parse_master_playlist(MASTER_PLAYLIST, &manifest_url()).unwrap();
+
+        let best = select_variant(&variants, "best").unwrap();
+        assert_eq!(best.resolution.as_deref(), Some("1920x1080"));
+
+        let worst = select_variant(&variants, "worst").unwrap();
+        assert_eq!(worst.resolution.as_deref(), Some("960x540"));
+    }
+
+    #[test]
+    fn select_variant_matches_exact_resolution() {
+        let (variants, _) = parse_master_playlist(MASTER_PLAYLIST, &manifest_url()).unwrap();
+
+        let exact = select_variant(&variants, "1280x720").unwrap();
+        assert_eq!(exact.bandwidth, 2_000_000);
+    }
+
+    #[test]
+    fn select_variant_returns_none_for_unadvertised_resolution() {
+        let (variants, _) = parse_master_playlist(MASTER_PLAYLIST, &manifest_url()).unwrap();
+
+        assert!(select_variant(&variants, "3840x2160").is_none());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::output::MarkdownOutput;
+use anyhow::Result;
+use url::Url;
+
+/// The media an extractor found alongside the page content, for the
+/// `--download` subsystem to fetch.
+pub struct MediaSources {
+    /// URL of the HLS master manifest for the session video. DASH
+    /// manifests are not detected.
+    pub hls_manifest: Option<String>,
+    /// URL of the WebVTT caption track, if the page links one.
+    pub captions_url: Option<String>,
+}
+
+/// A remote resource linked from the page, for the `--archive` subsystem
+/// to download and rewrite into a local link.
+pub struct LinkedResource {
+    pub title: String,
+    pub url: String,
+}
+
+/// Implemented by every parsed document so it can be rendered in any of
+/// the formats `--format` supports, regardless of which extractor produced it.
+pub trait ToMarkdown {
+    fn title(&self) -> &str;
+
+    /// Renders the document as Markdown. When `toc` is true, a
+    /// `## Contents` block of anchor links is inserted ahead of the body.
+    fn to_markdown(&self, toc: bool) -> MarkdownOutput;
+    fn to_json(&self) -> Result<String>;
+    fn to_yaml(&self) -> Result<String>;
+
+    /// Media the `--download` subsystem can fetch for this document, if
+    /// the underlying page links any. Defaults to none.
+    fn media_sources(&self) -> Option<MediaSources> {
+        None
+    }
+
+    /// Remote resources the `--archive` subsystem should download and
+    /// rewrite Markdown links to. Defaults to none.
+    fn linked_resources(&self) -> Vec<LinkedResource> {
+        Vec::new()
+    }
+}
+
+/// A self-contained recipe for turning one kind of Apple page into a
+/// [`ToMarkdown`] document. New page layouts (Tech Talks, sample-code
+/// project pages, documentation landing pages, ...) are added by
+/// implementing this trait in their own module under `extractors` and
+/// registering it in `extractors::all`, instead of growing a central
+/// `ContentType` enum and `match`.
+pub trait Extractor {
+    /// Returns true if this extractor knows how to handle `url`.
+    fn matches(&self, url: &Url) -> bool;
+
+    /// Fetches and parses `url`, returning the resulting document.
+    fn extract(&self, url: &Url) -> Result<Box<dyn ToMarkdown>>;
+
+    /// Short name used as the generated filename's content-type segment.
+    fn name(&self) -> &'static str;
+}
+
+/// Finds the first registered extractor willing to handle `url`.
+pub fn find_extractor<'a>(
+    extractors: &'a [Box<dyn Extractor>],
+    url: &Url,
+) -> Option<&'a dyn Extractor> {
+    extractors.iter().find(|e| e.matches(url)).map(|e| e.as_ref())
+}
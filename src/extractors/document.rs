@@ -0,0 +1,258 @@
+use super::{fetch_html, Config};
+use crate::extractor::{Extractor, LinkedResource, ToMarkdown};
+use crate::fetch;
+use crate::output::MarkdownOutput;
+use crate::toc::TocBuilder;
+use anyhow::{Context, Result};
+use headless_chrome::{Browser, LaunchOptionsBuilder};
+use scraper::{Html, Selector};
+use serde::Serialize;
+use url::Url;
+
+// Add WWDCDocument struct
+#[derive(Serialize)]
+pub struct WWDCDocument {
+    title: String,
+    description: String,
+    overview: String,
+    notes: Vec<String>,
+    sections: Vec<Section>,
+}
+
+#[derive(Serialize)]
+struct Section {
+    title: String,
+    items: Vec<DocumentItem>,
+}
+
+#[derive(Serialize)]
+struct DocumentItem {
+    title: String,
+    description: String,
+    url: String,
+    item_type: String,
+}
+
+impl ToMarkdown for WWDCDocument {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn to_markdown(&self, toc: bool) -> MarkdownOutput {
+        let mut toc_builder = TocBuilder::new();
+        let mut body = String::new();
+
+        let mut push_heading = |body: &mut String, level: u8, text: &str| {
+            if toc {
+                let slug = toc_builder.heading(level, text);
+                body.push_str(&format!("<a id=\"{slug}\"></a>\n"));
+            }
+            body.push_str(&format!("{} {}\n", "#".repeat(level as usize), text));
+        };
+
+        // Overview
+        push_heading(&mut body, 2, "Overview");
+        body.push_str(&format!("{}\n\n", self.overview));
+
+        // Notes
+        if !self.notes.is_empty() {
+            push_heading(&mut body, 2, "Notes");
+            for note in &self.notes {
+                body.push_str(&format!("{}\n\n", note));
+            }
+        }
+
+        // Sections
+        for section in &self.sections {
+            push_heading(&mut body, 2, &section.title);
+            body.push_str("\n");
+
+            for item in &section.items {
+                push_heading(&mut body, 3, &format!("{} `{}`", item.item_type, item.title));
+                body.push_str(&format!("{}\n\n", item.description));
+                body.push_str(&format!("[Documentation]({})\n\n", item.url));
+            }
+        }
+
+        // Title and description
+        let mut md = String::new();
+        md.push_str(&format!("# {}\n\n", self.title));
+        md.push_str(&format!("{}\n\n", self.description));
+        if toc {
+            md.push_str(&toc_builder.render());
+        }
+        md.push_str(&body);
+
+        MarkdownOutput {
+            content: md,
+            title: self.title.clone(),
+        }
+    }
+
+    fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    fn linked_resources(&self) -> Vec<LinkedResource> {
+        self.sections.iter()
+            .flat_map(|section| &section.items)
+            .map(|item| LinkedResource { title: item.title.clone(), url: item.url.clone() })
+            .collect()
+    }
+}
+
+fn parse_wwdc_document(url: &str, config: &Config) -> Result<WWDCDocument> {
+    let html = fetch_html(url, config, |opts| {
+        // Start headless browser
+        let options = LaunchOptionsBuilder::default()
+            .headless(true)
+            .build()?;
+
+        let browser = Browser::new(options)?;
+        let tab = browser.new_tab()?;
+
+        // Load page and wait for JavaScript execution, retrying transient
+        // navigation failures with backoff
+        fetch::navigate_with_retry(&tab, url, opts)?;
+
+        // Wait for JavaScript to load
+        tab.wait_for_element("h1")?;
+
+        // Get HTML content of the page
+        Ok(tab.get_content()?)
+    })?;
+
+    let document = Html::parse_document(&html);
+
+    // Get main title
+    let title_selector = Selector::parse("h1").unwrap();
+    let title = document.select(&title_selector)
+        .next()
+        .context("Title not found")?
+        .text()
+        .collect::<String>();
+
+    // Get meta description
+    let desc_selector = Selector::parse("meta[name='description']").unwrap();
+    let description = document.select(&desc_selector)
+        .next()
+        .and_then(|el| el.value().attr("content"))
+        .unwrap_or_default()
+        .to_string();
+
+    // Get overview content
+    let overview_selector = Selector::parse(".content > p").unwrap();
+    let overview = document.select(&overview_selector)
+        //.next()
+        .map(|el| el.text().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n");
+        //.unwrap_or_default();
+
+    // Get notes
+    let notes_selector = Selector::parse(".note").unwrap();
+    let notes: Vec<String> = document.select(&notes_selector)
+        .map(|note| {
+            let label = note.select(&Selector::parse(".label").unwrap())
+                .next()
+                .map(|el| el.text().collect::<String>())
+                .unwrap_or_default();
+
+            let content = note.select(&Selector::parse("p:not(.label)").unwrap())
+                .map(|el| el.text().collect::<String>())
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            format!("{}: {}", label, content)
+        })
+        .collect();
+
+    // Get sections
+    let section_selector = Selector::parse(".contenttable-section").unwrap();
+    let mut sections = Vec::new();
+
+    for section in document.select(&section_selector) {
+        let section_title = section
+            .select(&Selector::parse(".contenttable-title").unwrap())
+            .next()
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        let items = section
+            .select(&Selector::parse(".link-block").unwrap())
+            .map(|item| {
+
+                // Get title from either identifier or decorated-title
+                let title = item
+                    .select(&Selector::parse(".identifier, .decorated-title, code").unwrap())
+                    .next()
+                    .map(|el| {
+                        // Remove <wbr> tags and collect text
+                        el.text().collect::<Vec<_>>().join("")
+                            .replace("\u{200B}", "") // Remove zero-width space if any
+                    })
+                    .unwrap_or_else(|| {
+                        // Fallback to span text if no identifier/decorated-title
+                        item.select(&Selector::parse(".link span").unwrap())
+                            .next()
+                            .map(|el| el.text().collect::<Vec<_>>().join(""))
+                            .unwrap_or_default()
+                    });
+
+                DocumentItem {
+                    title,
+                    description: item.select(&Selector::parse(".content").unwrap())
+                        .next()
+                        .map(|el| el.text().collect())
+                        .unwrap_or_default(),
+                    url: item.select(&Selector::parse("a").unwrap())
+                        .next()
+                        .and_then(|el| el.value().attr("href"))
+                        .map(|href| format!("https://developer.apple.com{}", href))
+                        .unwrap_or_default(),
+                    item_type: item.select(&Selector::parse(".decorator").unwrap())
+                        .next()
+                        .map(|el| el.text().collect())
+                        .unwrap_or_else(|| "article".to_string()),
+                }
+            })
+            .collect();
+
+        sections.push(Section {
+            title: section_title,
+            items,
+        });
+    }
+
+    Ok(WWDCDocument {
+        title,
+        description,
+        overview,
+        notes,
+        sections,
+    })
+}
+
+/// Handles Apple documentation landing pages (`/documentation/...`):
+/// description, overview, notes and the linked content-table sections.
+pub struct DocumentExtractor {
+    pub config: Config,
+}
+
+impl Extractor for DocumentExtractor {
+    fn matches(&self, url: &Url) -> bool {
+        url.path().contains("/documentation/")
+    }
+
+    fn extract(&self, url: &Url) -> Result<Box<dyn ToMarkdown>> {
+        Ok(Box::new(parse_wwdc_document(url.as_str(), &self.config)?))
+    }
+
+    fn name(&self) -> &'static str {
+        "doc"
+    }
+}
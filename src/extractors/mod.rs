@@ -0,0 +1,74 @@
+mod document;
+mod video;
+
+use crate::cache::Cache;
+use crate::extractor::Extractor;
+use crate::fetch::FetchOptions;
+use anyhow::{bail, Result};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Shared behavior every extractor is built with: retry timeout and
+/// on-disk cache policy.
+#[derive(Clone)]
+pub struct Config {
+    pub timeout: Duration,
+    pub cache: Option<Arc<Cache>>,
+    pub refresh: bool,
+    pub offline: bool,
+    /// Show a progress spinner for this extractor's page fetch. Set to
+    /// `false` in batch mode, where worker threads fetch concurrently and
+    /// competing spinners would garble the terminal.
+    pub show_progress: bool,
+}
+
+impl Config {
+    pub fn fetch_options(&self) -> FetchOptions {
+        let opts = FetchOptions::new(self.timeout);
+        if self.show_progress {
+            opts.with_progress()
+        } else {
+            opts
+        }
+    }
+}
+
+/// Builds the registry of all known extractors, tried in order against
+/// the input URL. Add a new module and push it here to support another
+/// Apple page layout.
+pub fn all(config: Config) -> Vec<Box<dyn Extractor>> {
+    vec![
+        Box::new(video::VideoExtractor { config: config.clone() }),
+        Box::new(document::DocumentExtractor { config }),
+    ]
+}
+
+/// Resolves the raw HTML for `url` under `config`'s cache policy: serves
+/// a cached copy unless `--refresh` was passed, fails fast in `--offline`
+/// mode when nothing is cached, and otherwise runs `fetch_live` and
+/// stores its result for next time.
+pub fn fetch_html(
+    url: &str,
+    config: &Config,
+    fetch_live: impl FnOnce(&FetchOptions) -> Result<String>,
+) -> Result<String> {
+    if !config.refresh {
+        if let Some(cache) = &config.cache {
+            if let Some(html) = cache.get(url)? {
+                return Ok(html);
+            }
+        }
+    }
+
+    if config.offline {
+        bail!("--offline set and no cached entry for {url}");
+    }
+
+    let html = fetch_live(&config.fetch_options())?;
+
+    if let Some(cache) = &config.cache {
+        cache.put(url, &html)?;
+    }
+
+    Ok(html)
+}
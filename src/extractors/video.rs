@@ -0,0 +1,298 @@
+use super::{fetch_html, Config};
+use crate::extractor::{Extractor, LinkedResource, MediaSources, ToMarkdown};
+use crate::fetch;
+use crate::output::MarkdownOutput;
+use crate::toc::TocBuilder;
+use anyhow::{Context, Result};
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+use std::fmt;
+use url::Url;
+
+#[derive(Serialize)]
+pub struct WWDCVideo {
+    title: String,
+    url: String,
+    overview: String,
+    transcript: String,
+    code_samples: Vec<CodeSample>,
+    resources: Vec<Resource>,
+    #[serde(skip)]
+    hls_manifest: Option<String>,
+    #[serde(skip)]
+    captions_url: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CodeSample {
+    title: String,
+    timestamp: String,
+    code: String,
+    language: String,
+}
+
+#[derive(Serialize)]
+struct Resource {
+    title: String,
+    url: String,
+    resource_type: ResourceType,
+}
+
+#[derive(Debug, Serialize)]
+enum ResourceType {
+    Document,
+    Download,
+    Video,
+}
+
+impl fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResourceType::Document => write!(f, "Documentation"),
+            ResourceType::Download => write!(f, "Download"),
+            ResourceType::Video => write!(f, "Video"),
+        }
+    }
+}
+
+impl ToMarkdown for WWDCVideo {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn to_markdown(&self, toc: bool) -> MarkdownOutput {
+        let mut toc_builder = TocBuilder::new();
+        let mut body = String::new();
+
+        let mut push_heading = |body: &mut String, level: u8, text: &str| {
+            if toc {
+                let slug = toc_builder.heading(level, text);
+                body.push_str(&format!("<a id=\"{slug}\"></a>\n"));
+            }
+            body.push_str(&format!("{} {}\n", "#".repeat(level as usize), text));
+        };
+
+        // Overview
+        push_heading(&mut body, 2, "Overview");
+        body.push_str(&format!("{}\n\n", self.overview));
+
+        // Resources
+        if !self.resources.is_empty() {
+            push_heading(&mut body, 2, "Resources");
+            for resource in &self.resources {
+                body.push_str(&format!("- [{} ({})]({})\n",
+                    resource.title,
+                    resource.resource_type,
+                    resource.url
+                ));
+            }
+            body.push_str("\n");
+        }
+
+        // Code Samples
+        if !self.code_samples.is_empty() {
+            push_heading(&mut body, 2, "Code Samples");
+            for sample in &self.code_samples {
+                push_heading(&mut body, 3, &format!("{} ({})", sample.title, sample.timestamp));
+                body.push_str(&format!("```{}\n{}\n```\n\n", sample.language, sample.code));
+            }
+        }
+
+        // Transcript
+        if !self.transcript.is_empty() {
+            push_heading(&mut body, 2, "Transcript");
+            body.push_str(&format!("{}\n", self.transcript));
+        }
+
+        // Title and URL
+        let mut md = String::new();
+        md.push_str(&format!("# {}\n", self.title));
+        md.push_str(&format!("> {}\n\n", self.url));
+        if toc {
+            md.push_str(&toc_builder.render());
+        }
+        md.push_str(&body);
+
+        MarkdownOutput {
+            content: md,
+            title: self.title.clone(),
+        }
+    }
+
+    fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    fn media_sources(&self) -> Option<MediaSources> {
+        if self.hls_manifest.is_none() && self.captions_url.is_none() {
+            return None;
+        }
+
+        Some(MediaSources {
+            hls_manifest: self.hls_manifest.clone(),
+            captions_url: self.captions_url.clone(),
+        })
+    }
+
+    fn linked_resources(&self) -> Vec<LinkedResource> {
+        self.resources.iter()
+            .map(|resource| LinkedResource { title: resource.title.clone(), url: resource.url.clone() })
+            .collect()
+    }
+}
+
+fn parse_wwdc_video(url: &str, config: &Config) -> Result<WWDCVideo> {
+    let html = fetch_html(url, config, |opts| {
+        let client = reqwest::blocking::Client::new();
+        let response = fetch::send_with_retry(|| {
+            client.get(url)
+                .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+                .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7")
+                .header("Accept-Language", "en-US,en;q=0.9")
+                .header("Accept-Encoding", "gzip, deflate, br")
+                .header("Connection", "keep-alive")
+                .header("Upgrade-Insecure-Requests", "1")
+                .header("Sec-Fetch-Dest", "document")
+                .header("Sec-Fetch-Mode", "navigate")
+                .header("Sec-Fetch-Site", "none")
+                .header("Sec-Fetch-User", "?1")
+        }, opts)?;
+
+        Ok(response.text()?)
+    })?;
+
+    let document = Html::parse_document(&html);
+
+    // Selectors
+    let title_selector = Selector::parse("h1").unwrap();
+    let overview_selector = Selector::parse(".supplement.details > p").unwrap();
+    let transcript_selector = Selector::parse(".supplement.transcript .sentence").unwrap();
+    let code_selector = Selector::parse(".sample-code-main-container").unwrap();
+    let resources_selector = Selector::parse(".links.small li").unwrap();
+    let hls_selector = Selector::parse(
+        "video source[type='application/x-mpegurl'], video source[type='application/vnd.apple.mpegurl']"
+    ).unwrap();
+    let captions_selector = Selector::parse("track[kind='captions'], track[kind='subtitles']").unwrap();
+
+    // Extract title and overview
+    let title = document.select(&title_selector)
+        .next()
+        .context("Missing title")?
+        .text()
+        .collect::<String>();
+
+    let overview = document.select(&overview_selector)
+        .next()
+        .context("Missing overview")?
+        .text()
+        .collect::<String>();
+
+    // Extract transcript
+    let transcript = document.select(&transcript_selector)
+        .map(|element| element.text().collect::<String>())
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    // Extract code samples
+    let code_samples = document.select(&code_selector)
+        .map(|element| {
+            let title_elem = element.select(&Selector::parse("p").unwrap()).next();
+            let code_elem = element.select(&Selector::parse("code").unwrap()).next();
+
+            if let (Some(title), Some(code)) = (title_elem, code_elem) {
+                let title_text = title.text().collect::<String>();
+                // Extract timestamp from title (format: "10:40 - Setting scene association behavior")
+                let (timestamp, title) = if let Some(idx) = title_text.find(" - ") {
+                    (&title_text[..idx], &title_text[idx + 3..])
+                } else {
+                    ("", &title_text[..])
+                };
+
+                Some(CodeSample {
+                    title: title.to_string(),
+                    timestamp: timestamp.to_string(),
+                    code: code.text().collect::<String>(),
+                    language: "swift".to_string(), // Default to Swift for WWDC
+                })
+            } else {
+                None
+            }
+        })
+        .flatten()
+        .collect();
+
+    // Extract resources
+    let resources = document.select(&resources_selector)
+        .map(|element: ElementRef| {
+            let link = element.select(&Selector::parse("a").unwrap()).next()?;
+        let url = link.value().attr("href")?;
+        let title = link.text().collect::<String>();
+
+        // Simplified class check
+        let classes = element.value().attr("class").unwrap_or("");
+
+        let resource_type = if classes.contains("document") {
+            ResourceType::Document
+        } else if classes.contains("download") {
+            ResourceType::Download
+        } else if classes.contains("video") {
+            ResourceType::Video
+        } else {
+            ResourceType::Document // Default type
+        };
+
+        Some(Resource {
+            title,
+            url: url.to_string(),
+            resource_type,
+        })
+    })
+    .flatten()
+    .collect();
+
+    // Extract HLS manifest and caption track, if the page embeds a player
+    let hls_manifest = document.select(&hls_selector)
+        .next()
+        .and_then(|el| el.value().attr("src"))
+        .map(|src| src.to_string());
+
+    let captions_url = document.select(&captions_selector)
+        .next()
+        .and_then(|el| el.value().attr("src"))
+        .map(|src| src.to_string());
+
+    Ok(WWDCVideo {
+        title,
+        url: url.to_string(),
+        overview,
+        transcript,
+        code_samples,
+        resources,
+        hls_manifest,
+        captions_url,
+    })
+}
+
+/// Handles WWDC session video pages (`/videos/play/...`): overview,
+/// transcript, code samples and linked resources.
+pub struct VideoExtractor {
+    pub config: Config,
+}
+
+impl Extractor for VideoExtractor {
+    fn matches(&self, url: &Url) -> bool {
+        url.path().contains("/videos/play/")
+    }
+
+    fn extract(&self, url: &Url) -> Result<Box<dyn ToMarkdown>> {
+        Ok(Box::new(parse_wwdc_video(url.as_str(), &self.config)?))
+    }
+
+    fn name(&self) -> &'static str {
+        "video"
+    }
+}
@@ -0,0 +1,120 @@
+use anyhow::{bail, Context, Result};
+use headless_chrome::Tab;
+use indicatif::{ProgressBar, ProgressStyle};
+use rand::Rng;
+use reqwest::blocking::{RequestBuilder, Response};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const INITIAL_DELAY: Duration = Duration::from_millis(500);
+
+/// Retry policy shared by every network fetch in the crate, so a
+/// transient 5xx/timeout from Apple's CDN doesn't fail the whole run.
+pub struct FetchOptions {
+    /// Total time budget across all attempts, configurable via `--timeout`.
+    pub max_elapsed: Duration,
+    /// Whether to hide the progress spinner for this fetch. Hidden by
+    /// default: segment/resource-level fetches happen far too often (and,
+    /// in batch mode, concurrently across worker threads) for a
+    /// per-fetch spinner to stay readable. Opt in with `with_progress()`
+    /// for the handful of top-level, one-at-a-time page fetches.
+    quiet: bool,
+}
+
+impl FetchOptions {
+    pub fn new(max_elapsed: Duration) -> Self {
+        Self { max_elapsed, quiet: true }
+    }
+
+    pub fn with_progress(mut self) -> Self {
+        self.quiet = false;
+        self
+    }
+}
+
+/// Sends an HTTP request with exponential-backoff retry: doubling delay
+/// plus jitter, capped at `opts.max_elapsed`. Retries on connection
+/// errors, timeouts, and 429/5xx responses; fails fast on other 4xx.
+/// `build` is called once per attempt so the request can be re-issued
+/// (a sent `RequestBuilder` can't be resent as-is).
+pub fn send_with_retry<F>(mut build: F, opts: &FetchOptions) -> Result<Response>
+where
+    F: FnMut() -> RequestBuilder,
+{
+    let started = Instant::now();
+    let mut delay = INITIAL_DELAY;
+    let mut attempt: u32 = 1;
+
+    let progress = if opts.quiet {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new_spinner()
+    };
+    progress.set_style(ProgressStyle::with_template("{spinner} {msg}").unwrap());
+    progress.enable_steady_tick(Duration::from_millis(100));
+
+    loop {
+        progress.set_message(format!("attempt {attempt}"));
+
+        // Bound the attempt itself, not just the retry schedule between
+        // attempts — without this, a stalled connection can hang well
+        // past `--timeout` since reqwest otherwise waits indefinitely.
+        match build().timeout(opts.max_elapsed).send() {
+            Ok(response) if response.status().is_success() => {
+                progress.finish_and_clear();
+                return Ok(response);
+            }
+            Ok(response) => {
+                let status = response.status();
+                let retryable = status.is_server_error() || status.as_u16() == 429;
+                if !retryable {
+                    progress.finish_and_clear();
+                    bail!("request failed with status {status}");
+                }
+            }
+            Err(err) if !(err.is_timeout() || err.is_connect()) => {
+                progress.finish_and_clear();
+                return Err(err).context("request failed");
+            }
+            Err(_) => {}
+        }
+
+        if started.elapsed() + delay > opts.max_elapsed {
+            progress.finish_and_clear();
+            bail!("request timed out after {attempt} attempts ({:?} elapsed)", started.elapsed());
+        }
+
+        std::thread::sleep(delay + jitter(delay));
+        delay = (delay * 2).min(opts.max_elapsed);
+        attempt += 1;
+    }
+}
+
+/// Navigates a headless Chrome tab with the same backoff policy, for
+/// pages that need JavaScript execution rather than a plain HTTP fetch.
+pub fn navigate_with_retry(tab: &Arc<Tab>, url: &str, opts: &FetchOptions) -> Result<()> {
+    let started = Instant::now();
+    let mut delay = INITIAL_DELAY;
+    let mut attempt: u32 = 1;
+
+    loop {
+        let result = tab.navigate_to(url).and_then(|tab| tab.wait_until_navigated());
+        match result {
+            Ok(_) => return Ok(()),
+            Err(err) => {
+                if started.elapsed() + delay > opts.max_elapsed {
+                    return Err(err)
+                        .with_context(|| format!("navigating to {url} failed after {attempt} attempts"));
+                }
+                std::thread::sleep(delay + jitter(delay));
+                delay = (delay * 2).min(opts.max_elapsed);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+fn jitter(delay: Duration) -> Duration {
+    let max_jitter_ms = (delay.as_millis() as u64 / 2).max(1);
+    Duration::from_millis(rand::thread_rng().gen_range(0..max_jitter_ms))
+}
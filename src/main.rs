@@ -1,451 +1,145 @@
+mod archive;
+mod batch;
+mod cache;
+mod download;
+mod extractor;
+mod extractors;
+mod fetch;
+mod output;
+mod toc;
+
 use anyhow::{Context, Result};
-use scraper::{Html, Selector, ElementRef};  // Added Element trait
+use clap::Parser;
 use std::fs;
-use std::fmt;
-use clap::{Parser, ValueEnum};
-use headless_chrome::{Browser, LaunchOptionsBuilder};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
 
-struct WWDCVideo {
-    title: String,
-    url: String,
-    overview: String,
-    transcript: String,
-    code_samples: Vec<CodeSample>,
-    resources: Vec<Resource>,
-}
+use crate::cache::Cache;
+use crate::download::Container;
+use crate::extractor::find_extractor;
+use crate::extractors::Config;
+use crate::output::OutputFormat;
 
-struct CodeSample {
-    title: String,
-    timestamp: String,
-    code: String,
-    language: String,
-}
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Args {
+    /// URL of the WWDC content
+    #[arg(required_unless_present = "batch")]
+    url: Option<String>,
 
-struct Resource {
-    title: String,
-    url: String,
-    resource_type: ResourceType,
-}
+    /// Crawl a topic/year index page (or a local file of URLs, one per
+    /// line) and extract every linked session instead of a single URL
+    #[arg(long)]
+    batch: Option<String>,
 
-#[derive(Debug)]
-enum ResourceType {
-    Document,
-    Download,
-    Video,
-}
+    /// Number of sessions to extract concurrently in batch mode
+    #[arg(long, default_value_t = 4)]
+    concurrency: usize,
 
-impl fmt::Display for ResourceType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ResourceType::Document => write!(f, "Documentation"),
-            ResourceType::Download => write!(f, "Download"),
-            ResourceType::Video => write!(f, "Video"),
-        }
-    }
-}
+    /// Output format for the generated file
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Markdown)]
+    format: OutputFormat,
 
-impl WWDCVideo {
-    fn to_markdown(&self) -> MarkdownOutput {
-        let mut md = String::new();
-        
-        // Title and URL
-        md.push_str(&format!("# {}\n", self.title));
-        md.push_str(&format!("> {}\n\n", self.url));
-        
-        // Overview
-        md.push_str("## Overview\n");
-        md.push_str(&format!("{}\n\n", self.overview));
-        
-        // Resources
-        if !self.resources.is_empty() {
-            md.push_str("## Resources\n");
-            for resource in &self.resources {
-                md.push_str(&format!("- [{} ({})]({})\n", 
-                    resource.title,
-                    resource.resource_type,
-                    resource.url
-                ));
-            }
-            md.push_str("\n");
-        }
+    /// Also download the session video (HLS) and captions next to the
+    /// generated file
+    #[arg(long)]
+    download: bool,
 
-        // Code Samples
-        if !self.code_samples.is_empty() {
-            md.push_str("## Code Samples\n");
-            for sample in &self.code_samples {
-                md.push_str(&format!("### {} ({})\n", sample.title, sample.timestamp));
-                md.push_str(&format!("```{}\n{}\n```\n\n", sample.language, sample.code));
-            }
-        }
+    /// Representation to select when downloading: "best", "worst", or an
+    /// exact HLS RESOLUTION string (e.g. "1920x1080")
+    #[arg(long, default_value = "best")]
+    quality: String,
 
-        // Transcript
-        if !self.transcript.is_empty() {
-            md.push_str("## Transcript\n");
-            md.push_str(&format!("{}\n", self.transcript));
-        }
+    /// Container to mux the downloaded video into
+    #[arg(long, value_enum, default_value_t = Container::Mp4)]
+    container: Container,
 
-        MarkdownOutput {
-            content: md,
-            title: self.title.clone(),
-        }
-    }
-}
+    /// Max total time budget, in seconds, for retrying a single fetch
+    /// before giving up
+    #[arg(long, default_value_t = 30)]
+    timeout: u64,
 
-fn parse_wwdc_video(url: &str) -> Result<WWDCVideo> {
-    let client = reqwest::blocking::Client::new();
-    let response = client.get(url)
-        .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7")
-        .header("Accept-Language", "en-US,en;q=0.9")
-        .header("Accept-Encoding", "gzip, deflate, br")
-        .header("Connection", "keep-alive")
-        .header("Upgrade-Insecure-Requests", "1")
-        .header("Sec-Fetch-Dest", "document")
-        .header("Sec-Fetch-Mode", "navigate")
-        .header("Sec-Fetch-Site", "none")
-        .header("Sec-Fetch-User", "?1")
-        .send()?;
+    /// Prepend a "## Contents" block of anchor links to the generated
+    /// Markdown (ignored for --format json/yaml)
+    #[arg(long)]
+    toc: bool,
 
-    let html = response.text()?;
-    let document = Html::parse_document(&html);
+    /// Download every linked resource (sample code, PDFs, related videos)
+    /// into DIR and rewrite Markdown links to the local copies
+    #[arg(long, value_name = "DIR")]
+    archive: Option<String>,
 
-    // Selectors
-    let title_selector = Selector::parse("h1").unwrap();
-    let overview_selector = Selector::parse(".supplement.details > p").unwrap();
-    let transcript_selector = Selector::parse(".supplement.transcript .sentence").unwrap();
-    let code_selector = Selector::parse(".sample-code-main-container").unwrap();
-    let resources_selector = Selector::parse(".links.small li").unwrap();
+    /// Cache fetched pages as JSON fixtures under DIR, keyed by URL, and
+    /// reuse them on later runs instead of re-fetching
+    #[arg(long, value_name = "DIR")]
+    cache: Option<String>,
 
-    // Extract title and overview
-    let title = document.select(&title_selector)
-        .next()
-        .context("Missing title")?
-        .text()
-        .collect::<String>();
+    /// Ignore any cached entry and re-fetch, refreshing the cache
+    #[arg(long)]
+    refresh: bool,
 
-    let overview = document.select(&overview_selector)
-        .next()
-        .context("Missing overview")?
-        .text()
-        .collect::<String>();
+    /// Never hit the network; fail if a page isn't already in --cache
+    #[arg(long)]
+    offline: bool,
+}
 
-    // Extract transcript
-    let transcript = document.select(&transcript_selector)
-        .map(|element| element.text().collect::<String>())
-        .collect::<Vec<String>>()
-        .join(" ");
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let timeout = Duration::from_secs(args.timeout);
 
-    // Extract code samples
-    let code_samples = document.select(&code_selector)
-        .map(|element| {
-            let title_elem = element.select(&Selector::parse("p").unwrap()).next();
-            let code_elem = element.select(&Selector::parse("code").unwrap()).next();
-            
-            if let (Some(title), Some(code)) = (title_elem, code_elem) {
-                let title_text = title.text().collect::<String>();
-                // Extract timestamp from title (format: "10:40 - Setting scene association behavior")
-                let (timestamp, title) = if let Some(idx) = title_text.find(" - ") {
-                    (&title_text[..idx], &title_text[idx + 3..])
-                } else {
-                    ("", &title_text[..])
-                };
+    let cache = args.cache.as_deref().map(Cache::new).transpose()?.map(Arc::new);
+    if args.offline && cache.is_none() {
+        anyhow::bail!("--offline requires --cache DIR");
+    }
+    let config = Config { timeout, cache, refresh: args.refresh, offline: args.offline, show_progress: true };
 
-                Some(CodeSample {
-                    title: title.to_string(),
-                    timestamp: timestamp.to_string(),
-                    code: code.text().collect::<String>(),
-                    language: "swift".to_string(), // Default to Swift for WWDC
-                })
-            } else {
-                None
-            }
-        })
-        .flatten()
-        .collect();
+    if let Some(source) = &args.batch {
+        return batch::run(source, args.format, args.concurrency, config, args.toc);
+    }
 
-    // Extract resources
-    let resources = document.select(&resources_selector)
-        .map(|element: ElementRef| {
-            let link = element.select(&Selector::parse("a").unwrap()).next()?;
-        let url = link.value().attr("href")?;
-        let title = link.text().collect::<String>();
-        
-        // Simplified class check
-        let classes = element.value().attr("class").unwrap_or("");
-        
-        let resource_type = if classes.contains("document") {
-            ResourceType::Document
-        } else if classes.contains("download") {
-            ResourceType::Download
-        } else if classes.contains("video") {
-            ResourceType::Video
-        } else {
-            ResourceType::Document // Default type
-        };
+    let url = Url::parse(args.url.as_deref().context("Missing URL")?).context("Invalid URL")?;
 
-        Some(Resource {
-            title,
-            url: url.to_string(),
-            resource_type,
-        })
-    })
-    .flatten()
-    .collect();
+    let extractors = extractors::all(config);
+    let extractor = find_extractor(&extractors, &url)
+        .with_context(|| format!("No extractor registered for {}", url))?;
 
-    Ok(WWDCVideo {
-        title,
-        url: url.to_string(),
-        overview,
-        transcript,
-        code_samples,
-        resources,
-    })
-}
+    let document = extractor.extract(&url)?;
 
-#[derive(Debug, Clone, ValueEnum)]
-enum ContentType {
-    Video,
-    Document,
-}
+    let mut content = match args.format {
+        OutputFormat::Markdown => document.to_markdown(args.toc).content,
+        OutputFormat::Json => document.to_json()?,
+        OutputFormat::Yaml => document.to_yaml()?,
+    };
 
-#[derive(Parser, Debug)]
-#[command(author, version, about)]
-struct Args {
-    /// URL of the WWDC content
-    url: String,
+    if let Some(dir) = &args.archive {
+        if matches!(args.format, OutputFormat::Markdown) {
+            content = archive::run(document.linked_resources(), content, dir, timeout)?;
+        } else {
+            println!("--archive only rewrites Markdown output; skipping for this format");
+        }
+    }
 
-    /// Type of content to parse
-    #[arg(short, long, value_enum, default_value_t = ContentType::Video)]
-    content_type: ContentType,
-}
+    let base_name = format!("wwdc_{}_{}", extractor.name(), sanitize_filename(document.title()));
+    let filename = format!("{}.{}", base_name, args.format.extension());
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    
-    let markdown = match args.content_type {
-        ContentType::Video => {
-            let video = parse_wwdc_video(&args.url)?;
-            video.to_markdown()
-        }
-        ContentType::Document => {
-            let doc = parse_wwdc_document(&args.url)?;
-            doc.to_markdown()
+    fs::write(&filename, content)?;
+    println!("Generated {} file: {}", args.format.extension(), filename);
+
+    if args.download {
+        match document.media_sources() {
+            Some(media) => download::run(&media, &args.quality, args.container, &base_name, timeout)?,
+            None => println!("--download has no effect on this page: no media sources found"),
         }
-    };
-    
-    let content_type = match args.content_type {
-        ContentType::Video => "video",
-        ContentType::Document => "doc",
-    };
-    
-    let filename = format!("wwdc_{}_{}.md", 
-        content_type,
-        sanitize_filename(&markdown.title)
-    );
-    
-    fs::write(&filename, markdown.content)?;
-    println!("Generated markdown file: {}", filename);
+    }
+
     Ok(())
 }
 
-fn sanitize_filename(filename: &str) -> String {
+pub(crate) fn sanitize_filename(filename: &str) -> String {
     filename
         .replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|', ' '], "_")
         .to_lowercase()
         .trim()
         .to_string()
 }
-
-fn parse_wwdc_document(url: &str) -> Result<WWDCDocument> {
-    // Start headless browser
-    let options = LaunchOptionsBuilder::default()
-        .headless(true)
-        .build()?;
-    
-    let browser = Browser::new(options)?;
-    let tab = browser.new_tab()?;
-    
-    // Load page and wait for JavaScript execution
-    tab.navigate_to(url)?;
-    tab.wait_until_navigated()?;
-    
-    // Wait for JavaScript to load
-    tab.wait_for_element("h1")?;
-    
-    // Get HTML content of the page
-    let html = tab.get_content()?;
-    let document = Html::parse_document(&html);
-
-    // Get main title
-    let title_selector = Selector::parse("h1").unwrap();
-    let title = document.select(&title_selector)
-        .next()
-        .context("Title not found")?
-        .text()
-        .collect::<String>();
-
-    // Get meta description
-    let desc_selector = Selector::parse("meta[name='description']").unwrap();
-    let description = document.select(&desc_selector)
-        .next()
-        .and_then(|el| el.value().attr("content"))
-        .unwrap_or_default()
-        .to_string();
-
-    // Get overview content
-    let overview_selector = Selector::parse(".content > p").unwrap();
-    let overview = document.select(&overview_selector)
-        //.next()
-        .map(|el| el.text().collect::<String>())
-        .collect::<Vec<String>>()
-        .join("\n");
-        //.unwrap_or_default();
-
-    // Get notes
-    let notes_selector = Selector::parse(".note").unwrap();
-    let notes: Vec<String> = document.select(&notes_selector)
-        .map(|note| {
-            let label = note.select(&Selector::parse(".label").unwrap())
-                .next()
-                .map(|el| el.text().collect::<String>())
-                .unwrap_or_default();
-            
-            let content = note.select(&Selector::parse("p:not(.label)").unwrap())
-                .map(|el| el.text().collect::<String>())
-                .collect::<Vec<String>>()
-                .join("\n");
-
-            format!("{}: {}", label, content)
-        })
-        .collect();
-
-    // Get sections
-    let section_selector = Selector::parse(".contenttable-section").unwrap();
-    let mut sections = Vec::new();
-
-    for section in document.select(&section_selector) {
-        let section_title = section
-            .select(&Selector::parse(".contenttable-title").unwrap())
-            .next()
-            .map(|el| el.text().collect::<String>())
-            .unwrap_or_default();
-
-        let items = section
-            .select(&Selector::parse(".link-block").unwrap())
-            .map(|item| {
-
-                // Get title from either identifier or decorated-title
-                let title = item
-                    .select(&Selector::parse(".identifier, .decorated-title, code").unwrap())
-                    .next()
-                    .map(|el| {
-                        // Remove <wbr> tags and collect text
-                        el.text().collect::<Vec<_>>().join("")
-                            .replace("\u{200B}", "") // Remove zero-width space if any
-                    })
-                    .unwrap_or_else(|| {
-                        // Fallback to span text if no identifier/decorated-title
-                        item.select(&Selector::parse(".link span").unwrap())
-                            .next()
-                            .map(|el| el.text().collect::<Vec<_>>().join(""))
-                            .unwrap_or_default()
-                    });
-
-                DocumentItem {
-                    title,
-                    description: item.select(&Selector::parse(".content").unwrap())
-                        .next()
-                        .map(|el| el.text().collect())
-                        .unwrap_or_default(),
-                    url: item.select(&Selector::parse("a").unwrap())
-                        .next()
-                        .and_then(|el| el.value().attr("href"))
-                        .map(|href| format!("https://developer.apple.com{}", href))
-                        .unwrap_or_default(),
-                    item_type: item.select(&Selector::parse(".decorator").unwrap())
-                        .next()
-                        .map(|el| el.text().collect())
-                        .unwrap_or_else(|| "article".to_string()),
-                }
-            })
-            .collect();
-
-        sections.push(Section {
-            title: section_title,
-            items,
-        });
-    }
-
-    Ok(WWDCDocument {
-        title,
-        description,
-        overview,
-        notes,
-        sections,
-    })
-}
-
-// Add WWDCDocument struct
-struct WWDCDocument {
-    title: String,
-    description: String,
-    overview: String,
-    notes: Vec<String>,
-    sections: Vec<Section>,
-}
-
-struct Section {
-    title: String,
-    items: Vec<DocumentItem>,
-}
-
-struct DocumentItem {
-    title: String,
-    description: String,
-    url: String,
-    item_type: String,
-}
-
-// Önce yeni struct'ı ekleyelim
-struct MarkdownOutput {
-    content: String,
-    title: String,
-}
-
-impl WWDCDocument {
-    fn to_markdown(&self) -> MarkdownOutput {
-        let mut md = String::new();
-        
-        // Title and description
-        md.push_str(&format!("# {}\n\n", self.title));
-        md.push_str(&format!("{}\n\n", self.description));
-        
-        // Overview
-        md.push_str("## Overview\n");
-        md.push_str(&format!("{}\n\n", self.overview));
-        
-        // Notes
-        if !self.notes.is_empty() {
-            md.push_str("## Notes\n");
-            for note in &self.notes {
-                md.push_str(&format!("{}\n\n", note));
-            }
-        }
-        
-        // Sections
-        for section in &self.sections {
-            md.push_str(&format!("## {}\n\n", section.title));
-            
-            for item in &section.items {
-                md.push_str(&format!("### {} `{}`\n", item.item_type, item.title));
-                md.push_str(&format!("{}\n\n", item.description));
-                md.push_str(&format!("[Documentation]({})\n\n", item.url));
-            }
-        }
-        
-        MarkdownOutput {
-            content: md,
-            title: self.title.clone(),
-        }
-    }
-}
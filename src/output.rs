@@ -0,0 +1,25 @@
+use clap::ValueEnum;
+
+/// The rendered Markdown for a parsed document, plus the document's
+/// title.
+pub struct MarkdownOutput {
+    pub content: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum OutputFormat {
+    Markdown,
+    Json,
+    Yaml,
+}
+
+impl OutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "md",
+            OutputFormat::Json => "json",
+            OutputFormat::Yaml => "yaml",
+        }
+    }
+}
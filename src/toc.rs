@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+/// Collects headings as Markdown is generated and renders them as a
+/// nested `## Contents` block of `- [Title](#slug)` links, behind
+/// `--toc`.
+pub struct TocBuilder {
+    entries: Vec<(u8, String, String)>,
+    seen: HashMap<String, usize>,
+}
+
+impl TocBuilder {
+    pub fn new() -> Self {
+        Self { entries: Vec::new(), seen: HashMap::new() }
+    }
+
+    /// Records a heading at `level` and returns its anchor slug,
+    /// GitHub-style: lowercased, stripped of everything but
+    /// alphanumerics/spaces/hyphens, spaces turned into hyphens, and
+    /// duplicates disambiguated with `-1`, `-2`, ...
+    pub fn heading(&mut self, level: u8, text: &str) -> String {
+        let base = slugify(text);
+        let count = self.seen.entry(base.clone()).or_insert(0);
+        let slug = if *count == 0 { base } else { format!("{base}-{count}") };
+        *count += 1;
+
+        self.entries.push((level, text.to_string(), slug.clone()));
+        slug
+    }
+
+    /// Renders the recorded headings as a `## Contents` block, indenting
+    /// each entry by its depth relative to the shallowest heading seen.
+    pub fn render(&self) -> String {
+        if self.entries.is_empty() {
+            return String::new();
+        }
+
+        let min_level = self.entries.iter().map(|(level, _, _)| *level).min().unwrap();
+        let mut toc = String::from("## Contents\n\n");
+        for (level, text, slug) in &self.entries {
+            let indent = "  ".repeat((level - min_level) as usize);
+            toc.push_str(&format!("{indent}- [{text}](#{slug})\n"));
+        }
+        toc.push('\n');
+        toc
+    }
+}
+
+fn slugify(text: &str) -> String {
+    let slug: String = text
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-')
+        .collect();
+    slug.trim().replace(' ', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_strips_punctuation_and_lowercases() {
+        assert_eq!(slugify("Overview & Notes!"), "overview--notes");
+        assert_eq!(slugify("Code Samples"), "code-samples");
+    }
+
+    #[test]
+    fn heading_disambiguates_duplicate_slugs() {
+        let mut toc = TocBuilder::new();
+        assert_eq!(toc.heading(2, "Overview"), "overview");
+        assert_eq!(toc.heading(2, "Overview"), "overview-1");
+        assert_eq!(toc.heading(2, "Overview"), "overview-2");
+    }
+
+    #[test]
+    fn heading_treats_distinct_text_with_same_slug_as_duplicates() {
+        let mut toc = TocBuilder::new();
+        assert_eq!(toc.heading(2, "Code Samples"), "code-samples");
+        assert_eq!(toc.heading(3, "code samples"), "code-samples-1");
+    }
+
+    #[test]
+    fn render_indents_by_depth_relative_to_shallowest_heading() {
+        let mut toc = TocBuilder::new();
+        toc.heading(2, "Overview");
+        toc.heading(3, "Details");
+        let rendered = toc.render();
+        assert!(rendered.contains("- [Overview](#overview)\n"));
+        assert!(rendered.contains("  - [Details](#details)\n"));
+    }
+
+    #[test]
+    fn render_is_empty_with_no_headings() {
+        assert_eq!(TocBuilder::new().render(), "");
+    }
+}